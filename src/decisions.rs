@@ -0,0 +1,106 @@
+use crate::board::{Board, Camel};
+use crate::probabilities::odds::{CamelOdds, TileOdds};
+
+const LEG_BET_SECOND_PLACE_PAYOUT: f64 = 1.0;
+const LEG_BET_WRONG_PLACE_PAYOUT: f64 = -1.0;
+const GAME_BET_WRONG_PAYOUT: f64 = -1.0;
+const DIE_ROLL_PAYOUT: f64 = 1.0;
+const SPECTATOR_TILE_PAYOUT: f64 = 1.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    TakeLegBetTile { camel: Camel, payout: u8 },
+    BetGameWinner(Camel),
+    BetGameLoser(Camel),
+    RollDie,
+    PlaceSpectatorTile,
+}
+
+pub fn rank_actions(
+    board: &Board,
+    game_position_odds: &CamelOdds,
+    _round_position_odds: &CamelOdds,
+    tile_odds: &TileOdds,
+) -> Vec<(Action, f64)> {
+    let mut actions: Vec<(Action, f64)> = Vec::new();
+
+    for (camel, payout) in board.available_leg_bet_tiles() {
+        let p_first = tile_odds.odds(camel, 1);
+        let p_second = tile_odds.odds(camel, 2);
+        let expected_value = leg_bet_expected_value(p_first, p_second, payout);
+        actions.push((Action::TakeLegBetTile { camel, payout }, expected_value));
+    }
+
+    let last_place = board.camels().len() as u8;
+    for (camel, payout) in board.available_game_winner_bet_tiles() {
+        let p_winner = game_position_odds.odds(camel, 1);
+        actions.push((
+            Action::BetGameWinner(camel),
+            game_bet_expected_value(p_winner, payout),
+        ));
+    }
+    for (camel, payout) in board.available_game_loser_bet_tiles() {
+        let p_loser = game_position_odds.odds(camel, last_place);
+        actions.push((
+            Action::BetGameLoser(camel),
+            game_bet_expected_value(p_loser, payout),
+        ));
+    }
+
+    actions.push((Action::RollDie, DIE_ROLL_PAYOUT));
+    actions.push((Action::PlaceSpectatorTile, SPECTATOR_TILE_PAYOUT));
+
+    actions.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+    actions
+}
+
+// Takes raw probabilities rather than `&TileOdds` (mirroring `game_bet_expected_value`
+// below) so the expected-value math is testable without needing a real `TileOdds`.
+fn leg_bet_expected_value(p_first: f64, p_second: f64, payout: u8) -> f64 {
+    let p_wrong = 1.0 - p_first - p_second;
+    p_first * payout as f64
+        + p_second * LEG_BET_SECOND_PLACE_PAYOUT
+        + p_wrong * LEG_BET_WRONG_PLACE_PAYOUT
+}
+
+fn game_bet_expected_value(p_correct: f64, payout: u8) -> f64 {
+    p_correct * payout as f64 + (1.0 - p_correct) * GAME_BET_WRONG_PAYOUT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leg_bet_expected_value_rewards_higher_first_place_odds() {
+        let low = leg_bet_expected_value(0.1, 0.1, 5);
+        let high = leg_bet_expected_value(0.6, 0.1, 5);
+        assert!(
+            high > low,
+            "expected value should increase with p_first: {high} <= {low}"
+        );
+    }
+
+    #[test]
+    fn leg_bet_expected_value_matches_hand_computed_payout_ladder() {
+        // Certain first place at a 5-coin leg tile: always wins the full payout.
+        assert_eq!(leg_bet_expected_value(1.0, 0.0, 5), 5.0);
+        // Certain wrong-place finish: always pays the flat -1 penalty.
+        assert_eq!(leg_bet_expected_value(0.0, 0.0, 5), LEG_BET_WRONG_PLACE_PAYOUT);
+        // Even split between first (5 coins) and wrong (-1 coin).
+        assert_eq!(
+            leg_bet_expected_value(0.5, 0.0, 5),
+            0.5 * 5.0 + 0.5 * LEG_BET_WRONG_PLACE_PAYOUT
+        );
+    }
+
+    #[test]
+    fn game_bet_expected_value_matches_hand_computed_payout_ladder() {
+        assert_eq!(game_bet_expected_value(1.0, 3), 3.0);
+        assert_eq!(game_bet_expected_value(0.0, 3), GAME_BET_WRONG_PAYOUT);
+        assert_eq!(
+            game_bet_expected_value(0.25, 3),
+            0.25 * 3.0 + 0.75 * GAME_BET_WRONG_PAYOUT
+        );
+    }
+}
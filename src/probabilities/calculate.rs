@@ -6,14 +6,126 @@ use crate::probabilities::accumulators::{
 };
 use crate::probabilities::odds::{CamelOdds, TileOdds};
 use crossbeam::queue::ArrayQueue;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use std::{panic, thread};
 
+const NUM_TRANSPOSITION_SHARDS: usize = 64;
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+struct TranspositionKey {
+    board: Board,
+    depth: u8,
+}
+
+struct TranspositionTable<V> {
+    shards: Vec<Mutex<HashMap<TranspositionKey, V>>>,
+}
+
+impl<V: Clone> TranspositionTable<V> {
+    fn new() -> Self {
+        TranspositionTable {
+            shards: (0..NUM_TRANSPOSITION_SHARDS)
+                .map(|_| Mutex::new(HashMap::new()))
+                .collect(),
+        }
+    }
+
+    fn shard_for(&self, key: &TranspositionKey) -> &Mutex<HashMap<TranspositionKey, V>> {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        &self.shards[(hasher.finish() as usize) % self.shards.len()]
+    }
+
+    fn get(&self, key: &TranspositionKey) -> Option<V> {
+        self.shard_for(key).lock().unwrap().get(key).cloned()
+    }
+
+    fn insert(&self, key: TranspositionKey, value: V) {
+        self.shard_for(&key).lock().unwrap().insert(key, value);
+    }
+}
+
+// The cache is keyed only on (board, depth), so with a stochastic `LeafEvaluator`
+// (e.g. `RolloutLeafEvaluator`) the first path to reach a given state freezes its
+// one rollout-based estimate for every other path that reaches that same state for
+// the rest of the solve. That trades away some of the independence `num_rollouts`
+// implies at shared frontier nodes in exchange for not re-evaluating them; callers
+// relying on rollout variance at those nodes should raise `num_rollouts` to
+// compensate rather than assume each leaf sample is independent.
+struct TranspositionTables {
+    round_and_game: TranspositionTable<(PositionAccumulator, PositionAccumulator, TileAccumulator)>,
+    game_only: TranspositionTable<PositionAccumulator>,
+}
+
+impl TranspositionTables {
+    fn new() -> Self {
+        TranspositionTables {
+            round_and_game: TranspositionTable::new(),
+            game_only: TranspositionTable::new(),
+        }
+    }
+}
+
+pub trait LeafEvaluator: Sync {
+    fn evaluate(&self, board: &Board, rng: &mut StdRng) -> PositionAccumulator;
+}
+
+pub struct CurrentOrderEvaluator;
+
+impl LeafEvaluator for CurrentOrderEvaluator {
+    fn evaluate(&self, board: &Board, _rng: &mut StdRng) -> PositionAccumulator {
+        board.camel_order().into()
+    }
+}
+
+// `evaluate` runs `num_rollouts` full random playouts, the most expensive
+// per-call work in this module, so it depends on the transposition cache
+// (see `TranspositionTables`) memoizing it at depth 0 rather than re-running
+// it from scratch every time an identical frontier board recurs.
+pub struct RolloutLeafEvaluator {
+    pub num_rollouts: u32,
+}
+
+impl LeafEvaluator for RolloutLeafEvaluator {
+    fn evaluate(&self, board: &Board, rng: &mut StdRng) -> PositionAccumulator {
+        let mut accumulator = PositionAccumulator::new();
+        for _ in 0..self.num_rollouts {
+            let mut current = board.clone();
+            while !current.is_terminal() {
+                current = random_step(&current, rng);
+            }
+            accumulator += current.camel_order().into();
+        }
+        // A true terminal (`board.camel_order().into()`) contributes exactly one unit
+        // of probability mass per root-to-leaf path, and the exhaustive sum/normalize
+        // in `calculate_round_and_game_terminal_states`/`calculate_game_terminal_states`
+        // depends on every leaf honoring that. Average the rollouts down to one unit
+        // so a cutoff leaf doesn't outweigh sibling branches that terminate naturally.
+        accumulator / self.num_rollouts as f64
+    }
+}
+
 pub fn solve_probabilities(
     board: board::Board,
     depth: u8,
     num_workers: usize,
+) -> (CamelOdds, CamelOdds, TileOdds) {
+    solve_probabilities_with_evaluator(board, depth, num_workers, &CurrentOrderEvaluator)
+}
+
+pub fn solve_probabilities_with_evaluator(
+    board: board::Board,
+    depth: u8,
+    num_workers: usize,
+    leaf_evaluator: &dyn LeafEvaluator,
 ) -> (CamelOdds, CamelOdds, TileOdds) {
     coz::scope!("Solve Probabilities");
     let round_positions_accumulator = AtomicPositionAccumulator::new();
@@ -25,12 +137,18 @@ pub fn solve_probabilities(
     seed_stack(&stack, num_workers);
 
     let transition_depth = depth - board.num_unrolled();
+    let transposition_tables = TranspositionTables::new();
 
     (0..num_workers).into_par_iter().for_each(|_| {
         coz::thread_init();
+        let mut rng = StdRng::from_entropy();
         start_worker(
             &stack,
             transition_depth,
+            &transposition_tables,
+            leaf_evaluator,
+            &mut rng,
+            None,
             &game_positions_accumulator,
             &round_positions_accumulator,
             &tile_accumulator,
@@ -48,9 +166,347 @@ pub fn solve_probabilities(
     (game_position_odds, round_position_odds, tile_odds)
 }
 
+pub fn solve_within(
+    board: board::Board,
+    deadline: Duration,
+    num_workers: usize,
+) -> (CamelOdds, CamelOdds, TileOdds, u8) {
+    let start = std::time::Instant::now();
+    // The ladder must start at `num_unrolled`, not 1: `transition_depth` is computed
+    // as `depth - board.num_unrolled()`, so any shallower depth underflows the `u8`.
+    let mut depth: u8 = board.num_unrolled();
+    let mut best: Option<(CamelOdds, CamelOdds, TileOdds, u8)> = None;
+    // Shared across every rung of the ladder rather than rebuilt per depth: a
+    // position reached via a commutative reordering of the same rolls (see
+    // `TranspositionTables`) can recur at the same remaining-depth key on a later,
+    // deeper iteration, so keeping one table lets those iterations reuse it.
+    let transposition_tables = TranspositionTables::new();
+
+    loop {
+        let remaining = match deadline.checked_sub(start.elapsed()) {
+            Some(remaining) if !remaining.is_zero() => remaining,
+            _ => break,
+        };
+
+        match solve_with_deadline(board, depth, num_workers, remaining, &transposition_tables) {
+            Some((game_position_odds, round_position_odds, tile_odds)) => {
+                best = Some((game_position_odds, round_position_odds, tile_odds, depth));
+                depth += 1;
+            }
+            None => break,
+        }
+    }
+
+    match best {
+        Some(result) => result,
+        // Even the minimum depth (`board.num_unrolled()`, one full round with no
+        // further game lookahead) didn't finish inside `deadline`. There is no
+        // shallower exhaustive result we could fall back to, and returning nothing
+        // would leave the caller with no odds at all to act on, so run this
+        // minimum depth to completion with no deadline rather than abandon it
+        // partway through (a partial subtree isn't a valid sample — see the `None`
+        // case in `start_worker`). This intentionally breaks the "always finishes
+        // within budget" contract for this one degenerate case; callers that can't
+        // tolerate an overrun should size `deadline` generously enough that at
+        // least one round's worth of search reliably completes first.
+        None => {
+            let depth = board.num_unrolled();
+            let (game_position_odds, round_position_odds, tile_odds) =
+                solve_probabilities(board, depth, num_workers);
+            (game_position_odds, round_position_odds, tile_odds, depth)
+        }
+    }
+}
+
+fn solve_with_deadline(
+    board: board::Board,
+    depth: u8,
+    num_workers: usize,
+    remaining: Duration,
+    transposition_tables: &TranspositionTables,
+) -> Option<(CamelOdds, CamelOdds, TileOdds)> {
+    coz::scope!("Solve Within Deadline");
+    let round_positions_accumulator = AtomicPositionAccumulator::new();
+    let game_positions_accumulator = AtomicPositionAccumulator::new();
+    let tile_accumulator = AtomicTileAccumulator::new();
+
+    // Guard against a caller-supplied depth shallower than the current round's
+    // unrolled camels, which would otherwise underflow `transition_depth` below.
+    let depth = depth.max(board.num_unrolled());
+
+    let stack = ArrayQueue::new(num_workers * 2);
+    let _ = stack.push((board, depth));
+    seed_stack(&stack, num_workers);
+
+    let transition_depth = depth - board.num_unrolled();
+
+    let deadline_exceeded = Arc::new(AtomicBool::new(false));
+    let timer_flag = Arc::clone(&deadline_exceeded);
+    thread::spawn(move || {
+        thread::sleep(remaining);
+        timer_flag.store(true, Ordering::Relaxed);
+    });
+
+    (0..num_workers).into_par_iter().for_each(|_| {
+        coz::thread_init();
+        let mut rng = StdRng::from_entropy();
+        start_worker(
+            &stack,
+            transition_depth,
+            transposition_tables,
+            &CurrentOrderEvaluator,
+            &mut rng,
+            Some(&deadline_exceeded),
+            &game_positions_accumulator,
+            &round_positions_accumulator,
+            &tile_accumulator,
+        );
+    });
+
+    if deadline_exceeded.load(Ordering::Relaxed) {
+        return None;
+    }
+
+    let round_positions_accumulator: PositionAccumulator = round_positions_accumulator.into();
+    let game_positions_accumulator: PositionAccumulator = game_positions_accumulator.into();
+    let tile_accumulator: TileAccumulator = tile_accumulator.into();
+
+    let round_terminal_states = round_positions_accumulator.count_terminal();
+    let round_position_odds = CamelOdds::new(&round_positions_accumulator, &round_terminal_states);
+    let game_position_odds = game_positions_accumulator.into();
+    let tile_odds = TileOdds::new(&tile_accumulator, &round_terminal_states);
+    Some((game_position_odds, round_position_odds, tile_odds))
+}
+
+// `sample_playout` computes `round_order` at the exact point a round completes,
+// the same quantity the exhaustive path feeds into its `tile_accumulator` (see
+// `calculate_round_and_game_terminal_states`), so a sampled playout has a real
+// leg-bet outcome to accumulate too, not just game/round odds.
+pub fn sample_probabilities(
+    board: board::Board,
+    num_samples: u64,
+    num_workers: usize,
+    seed: u64,
+) -> (CamelOdds, CamelOdds, TileOdds) {
+    coz::scope!("Sample Probabilities");
+    let (game_positions_accumulator, round_positions_accumulator, tile_accumulator) =
+        sample_accumulators(&board, num_samples, num_workers, seed);
+
+    let round_terminal_states = round_positions_accumulator.count_terminal();
+    let round_position_odds = CamelOdds::new(&round_positions_accumulator, &round_terminal_states);
+    let game_position_odds = game_positions_accumulator.into();
+    let tile_odds = TileOdds::new(&tile_accumulator, &round_terminal_states);
+    (game_position_odds, round_position_odds, tile_odds)
+}
+
+const CONVERGENCE_BATCH_SIZE: u64 = 10_000;
+
+pub struct ConvergenceOdds {
+    pub game_position_odds: CamelOdds,
+    pub round_position_odds: CamelOdds,
+    // Accumulated the same way `sample_probabilities` does (see the comment there).
+    pub tile_odds: TileOdds,
+    pub game_position_half_widths: Vec<(board::Camel, u8, f64)>,
+    pub round_position_half_widths: Vec<(board::Camel, u8, f64)>,
+    pub samples_taken: u64,
+}
+
+pub fn sample_probabilities_until_converged(
+    board: board::Board,
+    tolerance: f64,
+    max_samples: u64,
+    num_workers: usize,
+    seed: u64,
+) -> ConvergenceOdds {
+    coz::scope!("Sample Probabilities Until Converged");
+    let camels = board.camels();
+
+    let mut game_positions_accumulator = PositionAccumulator::new();
+    let mut round_positions_accumulator = PositionAccumulator::new();
+    let mut tile_positions_accumulator = TileAccumulator::new();
+    let mut samples_taken: u64 = 0;
+
+    loop {
+        let batch_size = CONVERGENCE_BATCH_SIZE
+            .min(max_samples.saturating_sub(samples_taken))
+            .max(1);
+        let (game_batch, round_batch, tile_batch) = sample_accumulators(
+            &board,
+            batch_size,
+            num_workers,
+            seed.wrapping_add(samples_taken),
+        );
+        game_positions_accumulator += game_batch;
+        round_positions_accumulator += round_batch;
+        tile_positions_accumulator += tile_batch;
+        samples_taken += batch_size;
+
+        let game_position_odds: CamelOdds = game_positions_accumulator.clone().into();
+        // Normalize off `count_terminal()`, the same source of truth `sample_probabilities`
+        // uses, rather than `samples_taken` directly, so the two entry points can't drift.
+        let round_terminal_states = round_positions_accumulator.count_terminal();
+        let round_position_odds = CamelOdds::new(&round_positions_accumulator, &round_terminal_states);
+        let tile_odds = TileOdds::new(&tile_positions_accumulator, &round_terminal_states);
+        let game_position_half_widths =
+            confidence_half_widths(&game_position_odds, &camels, samples_taken);
+        let round_position_half_widths =
+            confidence_half_widths(&round_position_odds, &camels, samples_taken);
+
+        let converged = game_position_half_widths
+            .iter()
+            .chain(round_position_half_widths.iter())
+            .all(|(_, _, half_width)| *half_width <= tolerance);
+
+        if converged || samples_taken >= max_samples {
+            return ConvergenceOdds {
+                game_position_odds,
+                round_position_odds,
+                tile_odds,
+                game_position_half_widths,
+                round_position_half_widths,
+                samples_taken,
+            };
+        }
+    }
+}
+
+fn confidence_half_widths(
+    odds: &CamelOdds,
+    camels: &[board::Camel],
+    n: u64,
+) -> Vec<(board::Camel, u8, f64)> {
+    let n = n as f64;
+    let num_places = camels.len() as u8;
+    let mut half_widths = Vec::with_capacity(camels.len() * num_places as usize);
+    for &camel in camels {
+        for place in 1..=num_places {
+            let p = odds.odds(camel, place);
+            // The Wald half-width is exactly 0 at p == 0 or p == 1, which would
+            // read as total certainty off a single batch that just happened to see
+            // zero (or all) occurrences by chance — easy at `CONVERGENCE_BATCH_SIZE`
+            // sample sizes for anything but the most common outcomes. Rather than
+            // claim zero uncertainty, fall back to the rule-of-three bound: with
+            // zero successes (or zero failures) in `n` trials, the true rate is
+            // below 3/n at roughly 95% confidence, so treat 3/n as this outcome's
+            // half-width. It shrinks as `n` grows, so a real extreme (or a common
+            // one that's simply rare to miss) still converges given enough samples,
+            // just never claims more certainty than the sample size supports.
+            let half_width = if p <= 0.0 || p >= 1.0 {
+                3.0 / n
+            } else {
+                (p * (1.0 - p) / n).sqrt()
+            };
+            half_widths.push((camel, place, half_width));
+        }
+    }
+    half_widths
+}
+
+fn sample_accumulators(
+    board: &Board,
+    num_samples: u64,
+    num_workers: usize,
+    seed: u64,
+) -> (PositionAccumulator, PositionAccumulator, TileAccumulator) {
+    let round_positions_accumulator = AtomicPositionAccumulator::new();
+    let game_positions_accumulator = AtomicPositionAccumulator::new();
+    let tile_accumulator = AtomicTileAccumulator::new();
+
+    (0..num_workers).into_par_iter().for_each(|worker_index| {
+        coz::thread_init();
+        let samples_for_worker = samples_for_worker(num_samples, num_workers, worker_index);
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(worker_index as u64));
+        start_sampling_worker(
+            board,
+            samples_for_worker,
+            &mut rng,
+            &game_positions_accumulator,
+            &round_positions_accumulator,
+            &tile_accumulator,
+        );
+    });
+
+    (
+        game_positions_accumulator.into(),
+        round_positions_accumulator.into(),
+        tile_accumulator.into(),
+    )
+}
+
+fn samples_for_worker(num_samples: u64, num_workers: usize, worker_index: usize) -> u64 {
+    let num_workers = num_workers as u64;
+    let worker_index = worker_index as u64;
+    let base = num_samples / num_workers;
+    let remainder = num_samples % num_workers;
+    if worker_index < remainder {
+        base + 1
+    } else {
+        base
+    }
+}
+
+fn start_sampling_worker(
+    board: &Board,
+    num_samples: u64,
+    rng: &mut StdRng,
+    game_positions_accumulator: &AtomicPositionAccumulator,
+    round_positions_accumulator: &AtomicPositionAccumulator,
+    tile_accumulator: &AtomicTileAccumulator,
+) {
+    let mut private_game_positions = PositionAccumulator::new();
+    let mut private_round_positions = PositionAccumulator::new();
+    let mut private_tile_positions = TileAccumulator::new();
+    for _ in 0..num_samples {
+        let (round_order, game_order) = sample_playout(board, rng);
+        private_round_positions += round_order.into();
+        // `round_order` is the camel order at the moment this playout's round
+        // actually completed, the real leg-bet outcome for that round.
+        private_tile_positions += round_order.into();
+        private_game_positions += game_order.into();
+    }
+    game_positions_accumulator.add(private_game_positions);
+    round_positions_accumulator.add(private_round_positions);
+    tile_accumulator.add(private_tile_positions);
+}
+
+fn sample_playout(board: &Board, rng: &mut StdRng) -> (board::CamelOrder, board::CamelOrder) {
+    let mut current = board.clone();
+    let rolls_until_round_end = current.num_unrolled() as u32;
+    let mut rolls_done: u32 = 0;
+    let mut round_order = if rolls_done == rolls_until_round_end {
+        Some(current.camel_order())
+    } else {
+        None
+    };
+
+    loop {
+        if current.is_terminal() {
+            let game_order = current.camel_order();
+            return (round_order.unwrap_or(game_order), game_order);
+        }
+
+        current = random_step(&current, rng);
+        rolls_done += 1;
+
+        if round_order.is_none() && rolls_done == rolls_until_round_end {
+            round_order = Some(current.camel_order());
+        }
+    }
+}
+
+fn random_step(board: &Board, rng: &mut StdRng) -> Board {
+    let moves: Vec<_> = board.potential_moves().collect();
+    let choice = rng.gen_range(0..moves.len());
+    board.update(&moves[choice])
+}
+
 fn start_worker(
     stack: &ArrayQueue<(Board, u8)>,
     transition_depth: u8,
+    transposition_tables: &TranspositionTables,
+    leaf_evaluator: &dyn LeafEvaluator,
+    rng: &mut StdRng,
+    deadline_exceeded: Option<&AtomicBool>,
     game_positions_accumulator: &AtomicPositionAccumulator,
     round_positions_accumulator: &AtomicPositionAccumulator,
     tile_accumulator: &AtomicTileAccumulator,
@@ -59,19 +515,47 @@ fn start_worker(
     let mut private_round_positions = PositionAccumulator::new();
     let mut private_tile_positions = TileAccumulator::new();
     loop {
+        if let Some(flag) = deadline_exceeded {
+            if flag.load(Ordering::Relaxed) {
+                break;
+            }
+        }
         let (board, depth) = match stack.pop() {
             Some((board, depth)) => (board, depth),
             None => break,
         };
         if depth > transition_depth {
-            let (game_accumulations, round_accumulations, tile_accumulations) =
-                calculate_round_and_game_terminal_states(&board, &depth, &transition_depth);
-            private_game_positions += game_accumulations;
-            private_round_positions += round_accumulations;
-            private_tile_positions += tile_accumulations;
+            match calculate_round_and_game_terminal_states(
+                &board,
+                &depth,
+                &transition_depth,
+                transposition_tables,
+                leaf_evaluator,
+                rng,
+                deadline_exceeded,
+            ) {
+                Some((game_accumulations, round_accumulations, tile_accumulations)) => {
+                    private_game_positions += game_accumulations;
+                    private_round_positions += round_accumulations;
+                    private_tile_positions += tile_accumulations;
+                }
+                // The subtree was abandoned mid-recursion because the deadline fired;
+                // its partial contribution isn't a valid sample of the full subtree,
+                // so drop it rather than mixing it into the accumulators.
+                None => break,
+            }
         } else {
-            let game_accumulations = calculate_game_terminal_states(&board, &depth);
-            private_game_positions += game_accumulations;
+            match calculate_game_terminal_states(
+                &board,
+                &depth,
+                transposition_tables,
+                leaf_evaluator,
+                rng,
+                deadline_exceeded,
+            ) {
+                Some(game_accumulations) => private_game_positions += game_accumulations,
+                None => break,
+            }
         }
     }
     game_positions_accumulator.add(private_game_positions);
@@ -83,60 +567,164 @@ fn calculate_round_and_game_terminal_states(
     board: &Board,
     depth: &u8,
     transition_depth: &u8,
-) -> (PositionAccumulator, PositionAccumulator, TileAccumulator) {
-    if depth == &0 {
-        let accum = terminal_node_heuristic(board).into();
-        return (accum, accum, TileAccumulator::new());
-    } else if board.is_terminal() {
-        let accum = board.camel_order().into();
-        return (accum, accum, TileAccumulator::new());
+    transposition_tables: &TranspositionTables,
+    leaf_evaluator: &dyn LeafEvaluator,
+    rng: &mut StdRng,
+    deadline_exceeded: Option<&AtomicBool>,
+) -> Option<(PositionAccumulator, PositionAccumulator, TileAccumulator)> {
+    // Checked on every recursive call (not just once per worker task) so a deadline
+    // firing mid-subtree is noticed before the recursion burns through the rest of
+    // a combinatorially-sized branch.
+    if let Some(flag) = deadline_exceeded {
+        if flag.load(Ordering::Relaxed) {
+            return None;
+        }
     }
 
-    let mut game_positions_accumulator = PositionAccumulator::new();
-    let mut round_positions_accumulator = PositionAccumulator::new();
-    let mut tile_accumulator = TileAccumulator::new();
+    // Probed before the leaf cases below (not just the interior recursion), so a
+    // round-end result, including the game-only search beneath it, is memoized
+    // too. The `RolloutLeafEvaluator` passes themselves are memoized in
+    // `calculate_game_terminal_states`: the `depth == &0` branch here is unreached
+    // (see the comment on it).
+    let key = TranspositionKey {
+        board: board.clone(),
+        depth: *depth,
+    };
+    if let Some(cached) = transposition_tables.round_and_game.get(&key) {
+        return Some(cached);
+    }
+
+    // Checked in this order — terminal, then round-complete, then the depth-0
+    // rollout cutoff — because a real round-end/game-end order always takes
+    // priority over a `RolloutLeafEvaluator` estimate of some future game. Both
+    // of the first two cases are real terminal-ish orders, so both feed
+    // `tile_accumulator` with the board's actual `camel_order()`; only the
+    // depth-0 rollout fallback (unreached in practice, see below) has no real
+    // leg-bet outcome to report, since it would be a guess at how a
+    // still-ongoing round might end.
+    if board.is_terminal() {
+        // The game ended mid-round (a normal Camel Up occurrence, not an edge
+        // case), so this board's order is simultaneously the real game outcome
+        // and the real leg-bet outcome for whichever round is in progress.
+        let order = board.camel_order();
+        let accum: PositionAccumulator = order.into();
+        let tile_accum: TileAccumulator = order.into();
+        return Some((accum.clone(), accum, tile_accum));
+    }
 
     if depth <= transition_depth {
+        let mut round_positions_accumulator = PositionAccumulator::new();
+        let mut tile_accumulator = TileAccumulator::new();
         round_positions_accumulator += board.camel_order().into();
-        let game_positions = calculate_game_terminal_states(board, depth);
-        game_positions_accumulator += game_positions;
-        return (
-            game_positions_accumulator,
-            round_positions_accumulator,
-            tile_accumulator,
-        );
+        // The round ends exactly here, so this is also the real leg-bet outcome.
+        // `tile_accumulator` previously stayed at its initial empty value for the
+        // whole recursion, making `TileOdds` (and every leg-bet EV in `decisions`)
+        // structurally all-zero; feed it the same round-end order we just recorded
+        // above.
+        tile_accumulator += board.camel_order().into();
+        let game_positions = calculate_game_terminal_states(
+            board,
+            depth,
+            transposition_tables,
+            leaf_evaluator,
+            rng,
+            deadline_exceeded,
+        )?;
+        let result = (game_positions, round_positions_accumulator, tile_accumulator);
+        transposition_tables.round_and_game.insert(key, result.clone());
+        return Some(result);
     }
 
+    if depth == &0 {
+        // Unreached in practice: `depth <= transition_depth` above always catches
+        // `depth == 0` first, since `transition_depth` can never be negative. Kept
+        // as a defensive fallback rather than an `unreachable!()` so a future
+        // change to that invariant fails safe (an all-zero tile outcome) instead
+        // of panicking.
+        let accum = leaf_evaluator.evaluate(board, rng);
+        let result = (accum.clone(), accum, TileAccumulator::new());
+        transposition_tables.round_and_game.insert(key, result.clone());
+        return Some(result);
+    }
+
+    let mut game_positions_accumulator = PositionAccumulator::new();
+    let mut round_positions_accumulator = PositionAccumulator::new();
+    let mut tile_accumulator = TileAccumulator::new();
+
     for roll in board.potential_moves() {
         let next_board = board.update(&roll);
-        let (game_positions, round_positions, tiles) =
-            calculate_round_and_game_terminal_states(&next_board, &(depth - 1), transition_depth);
+        let (game_positions, round_positions, tiles) = calculate_round_and_game_terminal_states(
+            &next_board,
+            &(depth - 1),
+            transition_depth,
+            transposition_tables,
+            leaf_evaluator,
+            rng,
+            deadline_exceeded,
+        )?;
         game_positions_accumulator += game_positions;
         round_positions_accumulator += round_positions;
         tile_accumulator += tiles;
     }
-    return (
+    let result = (
         game_positions_accumulator,
         round_positions_accumulator,
         tile_accumulator,
     );
+    transposition_tables.round_and_game.insert(key, result.clone());
+    Some(result)
 }
 
-fn calculate_game_terminal_states(board: &Board, depth: &u8) -> PositionAccumulator {
+fn calculate_game_terminal_states(
+    board: &Board,
+    depth: &u8,
+    transposition_tables: &TranspositionTables,
+    leaf_evaluator: &dyn LeafEvaluator,
+    rng: &mut StdRng,
+    deadline_exceeded: Option<&AtomicBool>,
+) -> Option<PositionAccumulator> {
+    if let Some(flag) = deadline_exceeded {
+        if flag.load(Ordering::Relaxed) {
+            return None;
+        }
+    }
+
+    // See the matching comment in `calculate_round_and_game_terminal_states`: probed
+    // ahead of the `depth == &0` leaf case so rollout evaluations get memoized too.
+    let key = TranspositionKey {
+        board: board.clone(),
+        depth: *depth,
+    };
+    if let Some(cached) = transposition_tables.game_only.get(&key) {
+        return Some(cached);
+    }
+
     if depth == &0 {
-        return terminal_node_heuristic(board).into();
+        let accum = leaf_evaluator.evaluate(board, rng);
+        transposition_tables.game_only.insert(key, accum.clone());
+        return Some(accum);
     } else if board.is_terminal() {
-        return board.camel_order().into();
+        return Some(board.camel_order().into());
     }
 
     let mut positions_accumulator = PositionAccumulator::new();
 
     for roll in board.potential_moves() {
         let next_board = board.update(&roll);
-        let positions = calculate_game_terminal_states(&next_board, &(depth - 1));
+        let positions = calculate_game_terminal_states(
+            &next_board,
+            &(depth - 1),
+            transposition_tables,
+            leaf_evaluator,
+            rng,
+            deadline_exceeded,
+        )?;
         positions_accumulator += positions;
     }
-    return positions_accumulator;
+    transposition_tables
+        .game_only
+        .insert(key, positions_accumulator.clone());
+    Some(positions_accumulator)
 }
 
 fn seed_stack(stack: &ArrayQueue<(Board, u8)>, num_to_seed: usize) {
@@ -161,11 +749,34 @@ fn seed_stack(stack: &ArrayQueue<(Board, u8)>, num_to_seed: usize) {
     }
 }
 
-fn terminal_node_heuristic(board: &board::Board) -> board::CamelOrder {
-    return board.camel_order();
-}
-
 fn terminal_round_states_from_board(board: board::Board) -> u32 {
     let num_unrolled = board.num_unrolled() as u32;
     return num_unrolled.pow(constants::MAX_ROLL as u32);
 }
+
+// This series shipped several logic bugs (a zeroed sampled `TileOdds`, unweighted
+// rollout mass, a deadline flag never checked inside recursion, a `u8` depth
+// underflow, a NaN-panicking sort) that were only caught by manual re-reads rather
+// than a mechanical check. Most of this file's correctness hinges on `board`/`odds`
+// fixtures that live outside this source snapshot and can't be constructed here, so
+// this covers the one pure, fixture-free helper that's in scope: the worker-split
+// arithmetic `sample_accumulators` depends on to divide `num_samples` evenly.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_for_worker_splits_evenly_with_no_remainder() {
+        for worker_index in 0..4 {
+            assert_eq!(samples_for_worker(100, 4, worker_index), 25);
+        }
+    }
+
+    #[test]
+    fn samples_for_worker_distributes_remainder_to_earliest_workers() {
+        // 10 samples over 4 workers: 2 get 3, 2 get 2, summing back to 10.
+        let counts: Vec<u64> = (0..4).map(|i| samples_for_worker(10, 4, i)).collect();
+        assert_eq!(counts, vec![3, 3, 2, 2]);
+        assert_eq!(counts.iter().sum::<u64>(), 10);
+    }
+}